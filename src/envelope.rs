@@ -1,6 +1,11 @@
 use std::sync::Arc;
 
-use crate::SavoyParameters;
+use crate::params::Parameters;
+
+/// `ln(0)` is undefined, so segment endpoints never go below this floor. Attack
+/// rises from it up to `1.0`, and Release is considered finished once it falls
+/// back down to it.
+const FLOOR: f64 = 1e-4;
 
 pub enum EnvelopeStage {
     Off,
@@ -10,26 +15,141 @@ pub enum EnvelopeStage {
     Release,
 }
 
+/// Which of `Parameters`'s ADSR settings an [`Envelope`] reads from: one of
+/// the four FM operators, or the filter's own envelope.
+pub enum EnvelopeSource {
+    Operator(usize),
+    Filter,
+}
+
+impl EnvelopeSource {
+    fn attack(&self, params: &Parameters) -> f32 {
+        match self {
+            EnvelopeSource::Operator(index) => params.operators[*index].attack.get(),
+            EnvelopeSource::Filter => params.filter.attack.get(),
+        }
+    }
+
+    fn decay(&self, params: &Parameters) -> f32 {
+        match self {
+            EnvelopeSource::Operator(index) => params.operators[*index].decay.get(),
+            EnvelopeSource::Filter => params.filter.decay.get(),
+        }
+    }
+
+    fn sustain(&self, params: &Parameters) -> f32 {
+        match self {
+            EnvelopeSource::Operator(index) => params.operators[*index].sustain.get(),
+            EnvelopeSource::Filter => params.filter.sustain.get(),
+        }
+    }
+
+    fn release(&self, params: &Parameters) -> f32 {
+        match self {
+            EnvelopeSource::Operator(index) => params.operators[*index].release.get(),
+            EnvelopeSource::Filter => params.filter.release.get(),
+        }
+    }
+}
+
+/// A per-sample exponential ADSR, the shape analog envelope generators produce.
+///
+/// Each stage precomputes a constant multiplier from its start/end levels and
+/// length in samples; `process()` just multiplies `value` by that multiplier
+/// once per sample and watches for the stage's target to be crossed.
 pub struct Envelope {
     value: f64,
     stage: EnvelopeStage,
-    params: Arc<SavoyParameters>,
+    multiplier: f64,
+    sustain_level: f64,
+    sample_rate: f64,
+    source: EnvelopeSource,
+    params: Arc<Parameters>,
 }
 
 impl Envelope {
-    fn multiplier(start: f64, end: f64, length: f64) -> f64 {
-        1.0 + ((end.ln() - start.ln()) / length)
+    pub fn new(params: Arc<Parameters>, sample_rate: f64, source: EnvelopeSource) -> Self {
+        Envelope {
+            value: FLOOR,
+            stage: EnvelopeStage::Off,
+            multiplier: 1.0,
+            sustain_level: FLOOR,
+            sample_rate,
+            source,
+            params,
+        }
+    }
+
+    fn multiplier(start: f64, end: f64, length_in_samples: f64) -> f64 {
+        1.0 + (end.ln() - start.ln()) / length_in_samples
     }
 
-    fn process(&mut self, signal: f64, stage: EnvelopeStage) {
-        match stage {
-            EnvelopeStage::Off => {
+    /// Triggered on NoteOn: (re)starts the Attack stage from the floor.
+    pub fn note_on(&mut self) {
+        let length = (self.source.attack(&self.params) as f64 * self.sample_rate).max(1.0);
 
+        self.value = FLOOR;
+        self.multiplier = Self::multiplier(FLOOR, 1.0, length);
+        self.stage = EnvelopeStage::Attack;
+    }
+
+    /// Triggered on NoteOff: drops into Release from wherever the envelope
+    /// currently sits, regardless of stage.
+    pub fn note_off(&mut self) {
+        let length = (self.source.release(&self.params) as f64 * self.sample_rate).max(1.0);
+        let start = self.value.max(FLOOR);
+
+        self.multiplier = Self::multiplier(start, FLOOR, length);
+        self.stage = EnvelopeStage::Release;
+    }
+
+    fn enter_decay(&mut self) {
+        let length = (self.source.decay(&self.params) as f64 * self.sample_rate).max(1.0);
+
+        self.sustain_level = (self.source.sustain(&self.params) as f64).max(FLOOR);
+        self.multiplier = Self::multiplier(1.0, self.sustain_level, length);
+        self.stage = EnvelopeStage::Decay;
+    }
+
+    /// Advances the envelope by one sample and returns its current value.
+    pub fn process(&mut self) -> f64 {
+        match self.stage {
+            EnvelopeStage::Off => 0.0,
+            EnvelopeStage::Attack => {
+                self.value *= self.multiplier;
+
+                if self.value >= 1.0 {
+                    self.value = 1.0;
+                    self.enter_decay();
+                }
+
+                self.value
+            }
+            EnvelopeStage::Decay => {
+                self.value *= self.multiplier;
+
+                if self.value <= self.sustain_level {
+                    self.value = self.sustain_level;
+                    self.stage = EnvelopeStage::Sustain;
+                }
+
+                self.value
+            }
+            EnvelopeStage::Sustain => self.value,
+            EnvelopeStage::Release => {
+                self.value *= self.multiplier;
+
+                if self.value <= FLOOR {
+                    self.value = 0.0;
+                    self.stage = EnvelopeStage::Off;
+                }
+
+                self.value
             }
-            EnvelopeStage::Attack => {}
-            EnvelopeStage::Decay => {}
-            EnvelopeStage::Sustain => {}
-            EnvelopeStage::Release => {}
         }
     }
+
+    pub fn is_active(&self) -> bool {
+        !matches!(self.stage, EnvelopeStage::Off)
+    }
 }
@@ -0,0 +1,178 @@
+use std::f64::consts::TAU;
+use std::sync::Arc;
+
+use crate::envelope::{Envelope, EnvelopeSource};
+use crate::params::{Parameters, Waveform, OPERATOR_COUNT};
+
+/// Which operators modulate which, and which operators are audible, for one
+/// of the 8 classic FM routing topologies (modeled loosely on the YM2612's
+/// algorithm set). Modulator indices always point at an earlier operator, so
+/// a voice can resolve a whole algorithm in one pass over its operators.
+struct Algorithm {
+    modulators: [&'static [usize]; OPERATOR_COUNT],
+    carriers: [bool; OPERATOR_COUNT],
+}
+
+const ALGORITHMS: [Algorithm; 8] = [
+    // 0: serial chain 1->2->3->4, only op4 is heard.
+    Algorithm { modulators: [&[], &[0], &[1], &[2]], carriers: [false, false, false, true] },
+    // 1: op1 and op2 both feed op3, which feeds op4.
+    Algorithm { modulators: [&[], &[], &[0, 1], &[2]], carriers: [false, false, false, true] },
+    // 2: two independent 2-operator stacks, both audible.
+    Algorithm { modulators: [&[], &[], &[0], &[1]], carriers: [false, false, true, true] },
+    // 3: 1->2->3 stack, op4 carries on its own.
+    Algorithm { modulators: [&[], &[0], &[1], &[]], carriers: [false, false, true, true] },
+    // 4: two parallel 2-operator stacks, 1->2 and 3->4.
+    Algorithm { modulators: [&[], &[0], &[], &[2]], carriers: [false, true, false, true] },
+    // 5: op1 modulates the other three carriers in parallel.
+    Algorithm { modulators: [&[], &[0], &[0], &[0]], carriers: [false, true, true, true] },
+    // 6: 1->2 stack, op3 and op4 carry on their own.
+    Algorithm { modulators: [&[], &[0], &[], &[]], carriers: [false, true, true, true] },
+    // 7: all four operators summed in parallel.
+    Algorithm { modulators: [&[], &[], &[], &[]], carriers: [true, true, true, true] },
+];
+
+/// Quantizes the normalized 0..1 `Algorithm` parameter into an index, the
+/// same way `Waveform::from_normalized` quantizes the oscillator parameter.
+fn quantize_algorithm(raw: f32) -> usize {
+    (raw.clamp(0.0, 1.0) * (ALGORITHMS.len() - 1) as f32).round() as usize
+}
+
+/// Advances a small xorshift64 generator and returns a sample in -1..1, used
+/// for the noise waveform. Each operator keeps its own state so they don't
+/// all produce the same noise.
+fn next_noise(state: &mut u64) -> f64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+
+    (*state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
+
+fn generate(waveform: Waveform, phase: f64, noise_state: &mut u64) -> f64 {
+    match waveform {
+        Waveform::Sine => (TAU * phase).sin(),
+        Waveform::Saw => 2.0 * (phase - (phase + 0.5).floor()),
+        Waveform::Square => {
+            if phase < 0.5 {
+                1.0
+            } else {
+                -1.0
+            }
+        }
+        Waveform::Triangle => 4.0 * (phase - (phase + 0.75).floor() + 0.25).abs() - 1.0,
+        Waveform::Noise => next_noise(noise_state),
+    }
+}
+
+struct Operator {
+    phase: f64,
+    noise_state: u64,
+    envelope: Envelope,
+}
+
+impl Operator {
+    fn new(params: Arc<Parameters>, sample_rate: f64, index: usize) -> Self {
+        // A distinct, non-zero seed per operator so their noise streams differ.
+        let noise_state = 0x9E37_79B9_7F4A_7C15u64.wrapping_mul(index as u64 + 1);
+
+        let envelope = Envelope::new(params, sample_rate, EnvelopeSource::Operator(index));
+
+        Operator { phase: 0.0, noise_state, envelope }
+    }
+}
+
+/// A four-operator FM voice: each operator is a phase-accumulated oscillator
+/// of the selected [`Waveform`] whose phase is offset by its modulators' (and,
+/// for operator 1, its own previous) output, routed according to the selected
+/// [`Algorithm`].
+pub struct FmVoice {
+    operators: [Operator; OPERATOR_COUNT],
+    feedback_history: f64,
+    sample_rate: f64,
+    /// Normalized (0..1) velocity of the currently sounding note, blended into
+    /// the output gain by `Parameters::velocity_sensitivity`.
+    velocity: f64,
+    params: Arc<Parameters>,
+}
+
+impl FmVoice {
+    pub fn new(params: Arc<Parameters>, sample_rate: f64) -> Self {
+        let operators =
+            [0, 1, 2, 3].map(|index| Operator::new(Arc::clone(&params), sample_rate, index));
+
+        FmVoice { operators, feedback_history: 0.0, sample_rate, velocity: 1.0, params }
+    }
+
+    /// `velocity` is the triggering note's MIDI velocity, normalized to 0..1.
+    ///
+    /// Only output amplitude is scaled by velocity; each operator's attack
+    /// time comes from its own `OperatorParameters::attack` and does not
+    /// depend on velocity.
+    pub fn note_on(&mut self, velocity: f64) {
+        self.velocity = velocity;
+
+        for operator in self.operators.iter_mut() {
+            operator.envelope.note_on();
+        }
+    }
+
+    pub fn note_off(&mut self) {
+        for operator in self.operators.iter_mut() {
+            operator.envelope.note_off();
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.operators.iter().any(|operator| operator.envelope.is_active())
+    }
+
+    /// Renders one sample of the voice at `note_freq` Hz.
+    pub fn process(&mut self, note_freq: f64) -> f64 {
+        let algorithm = &ALGORITHMS[quantize_algorithm(self.params.algorithm.get())];
+        let feedback_depth = self.params.feedback.get() as f64;
+        let waveform = Waveform::from_normalized(self.params.oscillator.get());
+
+        let mut outputs = [0.0; OPERATOR_COUNT];
+
+        for index in 0..OPERATOR_COUNT {
+            let operator_params = &self.params.operators[index];
+            let ratio = operator_params.ratio_multiplier();
+            let level = operator_params.level.get() as f64;
+
+            let operator = &mut self.operators[index];
+            operator.phase += note_freq * ratio / self.sample_rate;
+            operator.phase -= operator.phase.floor();
+
+            let modulation: f64 = algorithm.modulators[index].iter().map(|&m| outputs[m]).sum();
+            let feedback = if index == 0 { feedback_depth * self.feedback_history } else { 0.0 };
+
+            let modulated_phase = {
+                let phase = operator.phase + (modulation + feedback) / TAU;
+                phase - phase.floor()
+            };
+
+            let envelope_amount = operator.envelope.process();
+            let sample =
+                generate(waveform, modulated_phase, &mut operator.noise_state) * level * envelope_amount;
+
+            outputs[index] = sample;
+        }
+
+        self.feedback_history = outputs[0];
+
+        let carrier_sum: f64 = algorithm
+            .carriers
+            .iter()
+            .zip(outputs.iter())
+            .filter_map(|(carrier, sample)| (*carrier).then_some(sample))
+            .sum();
+
+        // At 0 sensitivity every note plays at full level; at 1 it scales
+        // directly with how hard the key was struck.
+        let sensitivity = self.params.velocity_sensitivity.get() as f64;
+        let velocity_gain = 1.0 - sensitivity * (1.0 - self.velocity);
+
+        carrier_sum * velocity_gain
+    }
+}
@@ -1,13 +1,15 @@
 #[macro_use]
 extern crate vst;
 
+mod envelope;
+mod fm;
 mod params;
 
 use fundsp::hacker::*;
 
-use num_derive::FromPrimitive;
-
-use params::{Parameter, Parameters};
+use envelope::{Envelope, EnvelopeSource};
+use fm::FmVoice;
+use params::{Parameters, MAX_CUTOFF_HZ, MIN_CUTOFF_HZ};
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -18,24 +20,84 @@ use vst::plugin::{CanDo, Category, HostCallback, Info, Plugin, PluginParameters}
 
 use wmidi::{Note, Velocity};
 
+/// Maximum number of notes that can sound at once. Beyond this, a new NoteOn
+/// steals the oldest-triggered voice.
+const VOICE_COUNT: usize = 8;
+
+/// Post-FX chain applied to each voice's FM output: a resonant lowpass filter
+/// followed by anti-click smoothing and the stereo split. `pass()` carries
+/// the voice's externally-rendered audio, cutoff and resonance in, since both
+/// the oscillator and the filter envelope live outside the fundsp graph.
+fn build_audio_graph() -> Box<dyn AudioUnit64 + Send> {
+    Box::new((pass() | pass() | pass()) >> lowpass() >> declick() >> split::<U2>())
+}
+
+/// A single sounding note: its own FM engine, filter envelope and post-FX
+/// chain so voices don't interfere with one another's pitch or release tail.
+struct Voice {
+    note: Option<(Note, Velocity)>,
+    note_on_time: Duration,
+    /// The triggering note's frequency, held for the whole voice lifetime
+    /// (including its release tail) even after `note` is cleared on NoteOff.
+    frequency: f64,
+    fm: FmVoice,
+    filter_envelope: Envelope,
+    audio: Box<dyn AudioUnit64 + Send>,
+}
+
+impl Voice {
+    fn new(params: Arc<Parameters>, sample_rate: f64) -> Self {
+        let filter_envelope = Envelope::new(Arc::clone(&params), sample_rate, EnvelopeSource::Filter);
+
+        Voice {
+            note: None,
+            note_on_time: Duration::default(),
+            frequency: 440.0,
+            fm: FmVoice::new(params, sample_rate),
+            filter_envelope,
+            audio: build_audio_graph(),
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.fm.is_active()
+    }
+
+    /// `velocity` is the triggering note's MIDI velocity, normalized to 0..1.
+    fn note_on(&mut self, velocity: f64) {
+        self.fm.note_on(velocity);
+        self.filter_envelope.note_on();
+    }
+
+    fn note_off(&mut self) {
+        self.fm.note_off();
+        self.filter_envelope.note_off();
+    }
+}
+
 struct Savoy {
     sample_rate: f32,
     time: Duration,
-    note: Option<(Note, Velocity)>,
-    enabled: bool,
+    voices: Vec<Voice>,
     params: Arc<Parameters>,
-    audio: Box<dyn AudioUnit64 + Send>,
 }
 
 impl Savoy {
-    #[inline(always)]
-    fn set_tag(&mut self, tag: Tag, value: f64) {
-        self.audio.set(tag as i64, value);
-    }
+    /// Picks a voice for a new NoteOn: an idle voice if one exists, otherwise
+    /// the one whose note has been ringing the longest.
+    fn allocate_voice(&mut self) -> &mut Voice {
+        if let Some(index) = self.voices.iter().position(|voice| !voice.is_active()) {
+            return &mut self.voices[index];
+        }
+
+        let oldest = self.voices
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, voice)| voice.note_on_time)
+            .map(|(index, _)| index)
+            .unwrap_or(0);
 
-    #[inline(always)]
-    fn set_tag_with_param(&mut self, tag: Tag, param: Parameter) {
-        self.set_tag(tag, self.params.get_parameter(param as i32) as f64);
+        &mut self.voices[oldest]
     }
 }
 
@@ -47,68 +109,25 @@ impl Plugin for Savoy {
             inputs: 2,
             outputs: 2,
             category: Category::Synth,
-            parameters: 5,
+            parameters: 35,
+            presets: params::preset_count() as i32,
             ..Info::default()
         }
     }
 
     fn new(_host: HostCallback) -> Self {
-        let Parameters { oscillator: _, attack, decay, sustain, release } = Parameters::default();
+        let sample_rate = 44100.0;
+        let params = Arc::new(Parameters::default());
 
-        let offset_on = || tag(Tag::NoteOn as i64, 0.0);
-        let env_on = |attack: f64, decay: f64, sustain: f64| offset_on() >> envelope2(move |t, offset| {
-            let position = t - offset;
-
-            if position < attack {
-                position / attack
-            } else if position < decay + attack{
-                let decay_position = (position - attack) / decay;
-
-                (1.0 - decay_position) * (1.0 - sustain) + sustain
-            } else {
-                sustain
-            }
-        });
-
-        let offset_off = || tag(Tag::NoteOff as i64, 0.0);
-        let env_off = |release: f64| offset_off() >> envelope2(move |t, offset| {
-            // Somewhat hacky: using 0.0 as a sentinel value indicating that the 'off'
-            // envelope should be disabled when a note is playing.
-            if offset <= 0.0 {
-                return 1.0;
-            }
-
-            let position = t - offset;
-            if position < release {
-                1.0 - position / release
-            } else {
-                0.0
-            }
-        });
-
-
-        let attack = || tag(Tag::Attack as i64, attack.get() as f64);
-        let decay = || tag(Tag::Decay as i64, decay.get() as f64);
-        let sustain = || tag(Tag::Sustain as i64, sustain.get() as f64);
-        let release = || tag(Tag::Release as i64, release.get() as f64);
-
-        //let env = env_on(attack(), decay(), sustain()) * env_off(release());
-        //let offset = || tag(Tag::NoteOn as i64, 0.);
-        //let env = || offset() >> envelope2(|t, offset| downarc((t - offset) * 2.));
-
-        let freq = || tag(Tag::Freq as i64, 440.);
-
-        let audio_graph = freq() >> (sine() * freq()) >> (env_on(attack().value(), decay().value(), sustain().value()) * env_off(release().value()) * sine())
-            >> declick()
-            >> split::<U2>();
+        let voices = (0..VOICE_COUNT)
+            .map(|_| Voice::new(Arc::clone(&params), sample_rate as f64))
+            .collect();
 
         Self {
-            sample_rate: 44100.0,
+            sample_rate,
             time: Duration::default(),
-            note: None,
-            params: Arc::new(Parameters::default()),
-            audio: Box::new(audio_graph) as Box<dyn AudioUnit64 + Send>,
-            enabled: false,
+            voices,
+            params,
         }
     }
 
@@ -129,25 +148,64 @@ impl Plugin for Savoy {
                 .chunks_mut(MAX_BUFFER_SIZE)
                 .zip(right.chunks_mut(MAX_BUFFER_SIZE))
             {
-                let mut right_buffer = [0f64; MAX_BUFFER_SIZE];
                 let mut left_buffer = [0f64; MAX_BUFFER_SIZE];
+                let mut right_buffer = [0f64; MAX_BUFFER_SIZE];
+                let mut fm_buffer = [0f64; MAX_BUFFER_SIZE];
+                let mut cutoff_buffer = [0f64; MAX_BUFFER_SIZE];
+                let mut resonance_buffer = [0f64; MAX_BUFFER_SIZE];
+                let mut voice_left = [0f64; MAX_BUFFER_SIZE];
+                let mut voice_right = [0f64; MAX_BUFFER_SIZE];
 
-                self.set_tag_with_param(Tag::Attack, Parameter::Attack);
-                self.set_tag_with_param(Tag::Decay, Parameter::Decay);
-                self.set_tag_with_param(Tag::Sustain, Parameter::Sustain);
-                self.set_tag_with_param(Tag::Release, Parameter::Release);
+                self.time += Duration::from_secs_f32(MAX_BUFFER_SIZE as f32 / self.sample_rate);
 
-                if let Some((note, ..)) = self.note {
-                    self.set_tag(Tag::Freq, note.to_freq_f64())
-                }
+                let active_voices = self.voices.iter().filter(|voice| voice.is_active()).count();
+
+                for voice in self.voices.iter_mut() {
+                    if !voice.is_active() {
+                        continue;
+                    }
 
-                if self.enabled {
-                    self.time += Duration::from_secs_f32(MAX_BUFFER_SIZE as f32 / self.sample_rate);
-                    self.audio.process(
+                    let note_freq = voice.frequency;
+                    let base_cutoff = self.params.filter.cutoff_hz();
+                    let envelope_amount = self.params.filter.envelope_amount.get() as f64;
+                    let resonance = self.params.filter.resonance_q();
+
+                    for ((fm, cutoff), resonance_sample) in fm_buffer
+                        .iter_mut()
+                        .zip(cutoff_buffer.iter_mut())
+                        .zip(resonance_buffer.iter_mut())
+                    {
+                        *fm = voice.fm.process(note_freq);
+
+                        let envelope_value = voice.filter_envelope.process();
+                        *cutoff = (base_cutoff + envelope_amount * envelope_value * MAX_CUTOFF_HZ)
+                            .clamp(MIN_CUTOFF_HZ, MAX_CUTOFF_HZ);
+
+                        *resonance_sample = resonance;
+                    }
+
+                    voice.audio.process(
                         MAX_BUFFER_SIZE,
-                        &[],
-                        &mut [&mut left_buffer, &mut right_buffer],
+                        &[&fm_buffer, &cutoff_buffer, &resonance_buffer],
+                        &mut [&mut voice_left, &mut voice_right],
                     );
+
+                    for ((left, right), (voice_left, voice_right)) in left_buffer
+                        .iter_mut()
+                        .zip(right_buffer.iter_mut())
+                        .zip(voice_left.iter().zip(voice_right.iter()))
+                    {
+                        *left += voice_left;
+                        *right += voice_right;
+                    }
+                }
+
+                // Scale down by how many voices are actually sounding, so a
+                // single held note isn't punished for the full-chord case.
+                let headroom = 1.0 / active_voices.max(1) as f64;
+                for (left, right) in left_buffer.iter_mut().zip(right_buffer.iter_mut()) {
+                    *left *= headroom;
+                    *right *= headroom;
                 }
 
                 for (chunk, output) in left_chunk.iter_mut().zip(left_buffer.iter()) {
@@ -167,15 +225,20 @@ impl Plugin for Savoy {
                 if let Ok(midi) = wmidi::MidiMessage::try_from(midi.data.as_slice()) {
                     match midi {
                         wmidi::MidiMessage::NoteOn(_channel, note, velocity) => {
-                            self.set_tag(Tag::NoteOn, self.time.as_secs_f64());
-                            self.note = Some((note, velocity));
-                            self.enabled = true;
+                            let time = self.time;
+                            let velocity_gain = u8::from(velocity) as f64 / 127.0;
+                            let voice = self.allocate_voice();
+
+                            voice.note = Some((note, velocity));
+                            voice.note_on_time = time;
+                            voice.frequency = note.to_freq_f64();
+                            voice.note_on(velocity_gain);
                         }
                         wmidi::MidiMessage::NoteOff(_channel, note, _velocity) => {
-                            if let Some((current_note, ..)) = self.note {
-                                if current_note == note {
-                                    self.note = None;
-                                    self.set_tag(Tag::NoteOff, self.time.as_secs_f64());
+                            for voice in self.voices.iter_mut() {
+                                if voice.note.map(|(current_note, ..)| current_note) == Some(note) {
+                                    voice.note = None;
+                                    voice.note_off();
                                 }
                             }
                         }
@@ -189,20 +252,13 @@ impl Plugin for Savoy {
     fn set_sample_rate(&mut self, rate: f32) {
         self.sample_rate = rate;
         self.time = Duration::default();
-        self.audio.reset(Some(rate as f64));
-    }
-}
 
-#[derive(FromPrimitive, Clone, Copy)]
-pub enum Tag {
-    Oscillator = 0,
-    Attack = 1,
-    Decay = 2,
-    Sustain = 3,
-    Release = 4,
-    Freq = 5,
-    NoteOn = 6,
-    NoteOff = 7,
+        for voice in self.voices.iter_mut() {
+            voice.fm = FmVoice::new(Arc::clone(&self.params), rate as f64);
+            voice.filter_envelope = Envelope::new(Arc::clone(&self.params), rate as f64, EnvelopeSource::Filter);
+            voice.audio.reset(Some(rate as f64));
+        }
+    }
 }
 
 plugin_main!(Savoy);
@@ -2,22 +2,164 @@ use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
 use std::fmt::Display;
+use std::sync::atomic::{AtomicI32, Ordering};
 
 use vst::plugin::PluginParameters;
 use vst::util::AtomicFloat;
 
-pub struct Parameters {
-    pub oscillator: AtomicFloat,
+/// How many FM operators a voice has; see [`crate::fm`].
+pub const OPERATOR_COUNT: usize = 4;
+
+/// Total number of automatable parameters Savoy exposes; matches `Info::parameters`.
+pub const PARAMETER_COUNT: usize = 35;
+
+/// The shapes the `Oscillator` parameter selects between.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Waveform {
+    Sine,
+    Saw,
+    Square,
+    Triangle,
+    Noise,
+}
+
+impl Waveform {
+    const COUNT: usize = 5;
+
+    /// Quantizes the normalized 0..1 `Oscillator` parameter into a waveform.
+    pub fn from_normalized(value: f32) -> Self {
+        let index = (value.clamp(0.0, 1.0) * (Self::COUNT - 1) as f32).round() as usize;
+
+        match index {
+            0 => Waveform::Sine,
+            1 => Waveform::Saw,
+            2 => Waveform::Square,
+            3 => Waveform::Triangle,
+            _ => Waveform::Noise,
+        }
+    }
+}
+
+impl Display for Waveform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Waveform::Sine => "Sine",
+                Waveform::Saw => "Saw",
+                Waveform::Square => "Square",
+                Waveform::Triangle => "Triangle",
+                Waveform::Noise => "Noise",
+            }
+        )
+    }
+}
+
+/// Range the `OpN Ratio` parameters map into; see `OperatorParameters::ratio_multiplier`.
+const OPERATOR_RATIO_MIN: f64 = 0.5;
+const OPERATOR_RATIO_MAX: f64 = 16.0;
+
+/// One operator's frequency ratio, output level and ADSR. Every operator gets
+/// its own envelope so a modulator can, say, decay away long before its
+/// carrier does.
+pub struct OperatorParameters {
+    /// Normalized 0..1; see `ratio_multiplier` for the musical range it maps into.
+    pub ratio: AtomicFloat,
+    pub level: AtomicFloat,
     pub attack: AtomicFloat,
     pub decay: AtomicFloat,
     pub sustain: AtomicFloat,
     pub release: AtomicFloat,
 }
 
-impl Default for Parameters {
+impl Default for OperatorParameters {
     fn default() -> Self {
-        Parameters {
-            oscillator: AtomicFloat::new(0.0),
+        OperatorParameters {
+            // Maps to a ratio of 1.0, i.e. the operator tracks the fundamental.
+            ratio: AtomicFloat::new(0.2),
+            level: AtomicFloat::new(1.0),
+            attack: AtomicFloat::new(0.0),
+            decay: AtomicFloat::new(1.0),
+            sustain: AtomicFloat::new(1.0),
+            release: AtomicFloat::new(0.2),
+        }
+    }
+}
+
+impl OperatorParameters {
+    /// Maps the normalized `ratio` parameter onto a musically useful
+    /// frequency-ratio range (0.5x-16x), log-scaled.
+    pub fn ratio_multiplier(&self) -> f64 {
+        let normalized = self.ratio.get().clamp(0.0, 1.0) as f64;
+        OPERATOR_RATIO_MIN * (OPERATOR_RATIO_MAX / OPERATOR_RATIO_MIN).powf(normalized)
+    }
+}
+
+#[derive(FromPrimitive, Clone, Copy)]
+enum OperatorField {
+    Ratio,
+    Level,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+impl OperatorParameters {
+    fn get(&self, field: OperatorField) -> f32 {
+        match field {
+            OperatorField::Ratio => self.ratio.get(),
+            OperatorField::Level => self.level.get(),
+            OperatorField::Attack => self.attack.get(),
+            OperatorField::Decay => self.decay.get(),
+            OperatorField::Sustain => self.sustain.get(),
+            OperatorField::Release => self.release.get(),
+        }
+    }
+
+    fn set(&self, field: OperatorField, value: f32) {
+        match field {
+            OperatorField::Ratio => self.ratio.set(value),
+            OperatorField::Level => self.level.set(value),
+            OperatorField::Attack => self.attack.set(value),
+            OperatorField::Decay => self.decay.set(value),
+            OperatorField::Sustain => self.sustain.set(value),
+            OperatorField::Release => self.release.set(value),
+        }
+    }
+}
+
+/// Musically-safe bounds for the filter cutoff, so the envelope can't drive
+/// it somewhere the state-variable filter becomes unstable.
+pub const MIN_CUTOFF_HZ: f64 = 20.0;
+pub const MAX_CUTOFF_HZ: f64 = 20_000.0;
+
+/// Bound on resonance (Q); past this the state-variable filter self-oscillates.
+pub const MAX_RESONANCE: f64 = 10.0;
+
+/// The resonant lowpass filter's cutoff/resonance and its own envelope, which
+/// can sweep the cutoff independently of the amplitude envelopes.
+pub struct FilterParameters {
+    /// Normalized 0..1; see `cutoff_hz` for the Hz range it maps into.
+    pub cutoff: AtomicFloat,
+    /// Normalized 0..1; see `resonance_q` for the Q range it maps into.
+    pub resonance: AtomicFloat,
+    pub envelope_amount: AtomicFloat,
+    pub attack: AtomicFloat,
+    pub decay: AtomicFloat,
+    pub sustain: AtomicFloat,
+    pub release: AtomicFloat,
+}
+
+impl Default for FilterParameters {
+    fn default() -> Self {
+        FilterParameters {
+            // Fully open: maps to MAX_CUTOFF_HZ.
+            cutoff: AtomicFloat::new(1.0),
+            // Maps to a Q of 1.0, i.e. no resonant peak.
+            resonance: AtomicFloat::new(0.1),
+            envelope_amount: AtomicFloat::new(0.0),
             attack: AtomicFloat::new(0.0),
             decay: AtomicFloat::new(1.0),
             sustain: AtomicFloat::new(1.0),
@@ -26,26 +168,229 @@ impl Default for Parameters {
     }
 }
 
+impl FilterParameters {
+    /// Maps the normalized `cutoff` parameter log-scaled onto `MIN_CUTOFF_HZ..MAX_CUTOFF_HZ`.
+    pub fn cutoff_hz(&self) -> f64 {
+        let normalized = self.cutoff.get().clamp(0.0, 1.0) as f64;
+        MIN_CUTOFF_HZ * (MAX_CUTOFF_HZ / MIN_CUTOFF_HZ).powf(normalized)
+    }
+
+    /// Maps the normalized `resonance` parameter onto `0.0..MAX_RESONANCE`.
+    pub fn resonance_q(&self) -> f64 {
+        self.resonance.get().clamp(0.0, 1.0) as f64 * MAX_RESONANCE
+    }
+}
+
+#[derive(FromPrimitive, Clone, Copy)]
+enum FilterField {
+    Cutoff,
+    Resonance,
+    EnvelopeAmount,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+impl FilterParameters {
+    fn get(&self, field: FilterField) -> f32 {
+        match field {
+            FilterField::Cutoff => self.cutoff.get(),
+            FilterField::Resonance => self.resonance.get(),
+            FilterField::EnvelopeAmount => self.envelope_amount.get(),
+            FilterField::Attack => self.attack.get(),
+            FilterField::Decay => self.decay.get(),
+            FilterField::Sustain => self.sustain.get(),
+            FilterField::Release => self.release.get(),
+        }
+    }
+
+    fn set(&self, field: FilterField, value: f32) {
+        match field {
+            FilterField::Cutoff => self.cutoff.set(value),
+            FilterField::Resonance => self.resonance.set(value),
+            FilterField::EnvelopeAmount => self.envelope_amount.set(value),
+            FilterField::Attack => self.attack.set(value),
+            FilterField::Decay => self.decay.set(value),
+            FilterField::Sustain => self.sustain.set(value),
+            FilterField::Release => self.release.set(value),
+        }
+    }
+}
+
+pub struct Parameters {
+    pub oscillator: AtomicFloat,
+    /// Which of the 8 FM routing topologies to use; see `fm::ALGORITHMS`.
+    pub algorithm: AtomicFloat,
+    /// Depth of operator 1's self-feedback path.
+    pub feedback: AtomicFloat,
+    /// How strongly MIDI velocity scales output amplitude: 0.0 plays every
+    /// note at full level, 1.0 scales it directly by velocity.
+    pub velocity_sensitivity: AtomicFloat,
+    pub operators: [OperatorParameters; OPERATOR_COUNT],
+    pub filter: FilterParameters,
+    /// Index of the current factory preset, set by `change_preset` (which also
+    /// loads it) or `set_preset_num` (which does not), and reported back
+    /// through `get_preset_num`. Not itself a saved parameter.
+    current_preset: AtomicI32,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Parameters {
+            oscillator: AtomicFloat::new(0.0),
+            algorithm: AtomicFloat::new(0.0),
+            feedback: AtomicFloat::new(0.0),
+            velocity_sensitivity: AtomicFloat::new(1.0),
+            operators: Default::default(),
+            filter: FilterParameters::default(),
+            current_preset: AtomicI32::new(0),
+        }
+    }
+}
+
+/// Version written at the start of preset/bank blobs. `deserialize_parameters`
+/// rejects blobs carrying any other version.
+const PRESET_FORMAT_VERSION: u32 = 1;
+
+fn serialize_parameters(values: &[f32]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(4 + values.len() * 4);
+    data.extend_from_slice(&PRESET_FORMAT_VERSION.to_le_bytes());
+
+    for value in values {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    data
+}
+
+/// Reads back whatever floats follow the version header, or `None` if the
+/// header is missing or from a format version this build doesn't understand.
+/// Extra floats past the ones the caller knows about (e.g. a same-version blob
+/// with more parameters) are returned for the caller to ignore; a trailing
+/// partial float is dropped.
+fn deserialize_parameters(data: &[u8]) -> Option<Vec<f32>> {
+    if data.len() < 4 {
+        return None;
+    }
+
+    let version = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    if version != PRESET_FORMAT_VERSION {
+        return None;
+    }
+
+    Some(
+        data[4..]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect(),
+    )
+}
+
+/// A built-in patch: a name plus sparse overrides applied on top of
+/// `Parameters::default()`. Anything not listed keeps its default value.
+struct FactoryPreset {
+    name: &'static str,
+    overrides: &'static [(Parameter, f32)],
+}
+
+const FACTORY_PRESETS: &[FactoryPreset] = &[
+    FactoryPreset { name: "Init", overrides: &[] },
+    FactoryPreset {
+        name: "Bell FM",
+        overrides: &[
+            // Algorithm 7 (all parallel carriers), normalized as index/7.
+            (Parameter::Algorithm, 1.0),
+            // Ratio 3.5x, normalized through `ratio_multiplier`'s 0.5x-16x log range.
+            (Parameter::Op2Ratio, 0.5615),
+            (Parameter::Op2Level, 0.6),
+            (Parameter::Op1Release, 1.2),
+        ],
+    },
+    FactoryPreset {
+        name: "Pluck Bass",
+        overrides: &[
+            // Algorithm 0 (serial 1->2->3->4 chain).
+            (Parameter::Algorithm, 0.0),
+            (Parameter::Op1Decay, 0.15),
+            (Parameter::Op1Sustain, 0.0),
+            // 800 Hz, normalized through `cutoff_hz`'s 20 Hz-20 kHz log range.
+            (Parameter::FilterCutoff, 0.534),
+            (Parameter::FilterEnvelopeAmount, 0.8),
+            (Parameter::FilterDecay, 0.25),
+        ],
+    },
+    FactoryPreset {
+        name: "Wobble Pad",
+        overrides: &[
+            // Algorithm 5 (op1 modulates the other three in parallel), normalized as index/7.
+            (Parameter::Algorithm, 0.7143),
+            // Ratio 0.5x, the bottom of `ratio_multiplier`'s range.
+            (Parameter::Op1Ratio, 0.0),
+            (Parameter::Feedback, 0.4),
+            (Parameter::Op1Attack, 0.4),
+            (Parameter::Op1Release, 1.5),
+        ],
+    },
+    FactoryPreset {
+        name: "Noise Hit",
+        overrides: &[
+            (Parameter::Oscillator, 1.0),
+            (Parameter::Op1Decay, 0.05),
+            (Parameter::Op1Sustain, 0.0),
+            // 6000 Hz, normalized through `cutoff_hz`'s 20 Hz-20 kHz log range.
+            (Parameter::FilterCutoff, 0.8257),
+        ],
+    },
+];
+
+/// How many factory presets ship with Savoy; used to size `Info::presets`.
+pub fn preset_count() -> usize {
+    FACTORY_PRESETS.len()
+}
+
+/// Renders a factory preset's sparse overrides into a full set of parameter
+/// values, in `Parameter` index order.
+fn factory_preset_values(preset: &FactoryPreset) -> Vec<f32> {
+    let defaults = Parameters::default();
+    let mut values: Vec<f32> =
+        (0..PARAMETER_COUNT as i32).map(|index| defaults.get_parameter(index)).collect();
+
+    for (parameter, value) in preset.overrides {
+        values[*parameter as usize] = *value;
+    }
+
+    values
+}
+
 impl PluginParameters for Parameters {
     fn get_parameter(&self, index: i32) -> f32 {
         match FromPrimitive::from_i32(index) {
             Some(Parameter::Oscillator) => self.oscillator.get(),
-            Some(Parameter::Attack) => self.attack.get(),
-            Some(Parameter::Decay) => self.decay.get(),
-            Some(Parameter::Sustain) => self.sustain.get(),
-            Some(Parameter::Release) => self.release.get(),
-            _ => 0.0,
+            Some(Parameter::Algorithm) => self.algorithm.get(),
+            Some(Parameter::Feedback) => self.feedback.get(),
+            Some(Parameter::VelocitySensitivity) => self.velocity_sensitivity.get(),
+            Some(parameter) => match parameter.target() {
+                Some(ParameterTarget::Operator(operator, field)) => self.operators[operator].get(field),
+                Some(ParameterTarget::Filter(field)) => self.filter.get(field),
+                None => 0.0,
+            },
+            None => 0.0,
         }
     }
 
     fn set_parameter(&self, index: i32, value: f32) {
         match FromPrimitive::from_i32(index) {
             Some(Parameter::Oscillator) => self.oscillator.set(value),
-            Some(Parameter::Attack) => self.attack.set(value),
-            Some(Parameter::Decay) => self.decay.set(value),
-            Some(Parameter::Sustain) => self.sustain.set(value),
-            Some(Parameter::Release) => self.release.set(value),
-            _ => (),
+            Some(Parameter::Algorithm) => self.algorithm.set(value),
+            Some(Parameter::Feedback) => self.feedback.set(value),
+            Some(Parameter::VelocitySensitivity) => self.velocity_sensitivity.set(value),
+            Some(parameter) => match parameter.target() {
+                Some(ParameterTarget::Operator(operator, field)) => self.operators[operator].set(field, value),
+                Some(ParameterTarget::Filter(field)) => self.filter.set(field, value),
+                None => (),
+            },
+            None => (),
         }
     }
 
@@ -55,15 +400,158 @@ impl PluginParameters for Parameters {
             .map(|f| f.to_string())
             .unwrap_or_else(|| "unknown".to_string())
     }
+
+    fn get_parameter_text(&self, index: i32) -> String {
+        match FromPrimitive::from_i32(index) {
+            Some(Parameter::Oscillator) => Waveform::from_normalized(self.oscillator.get()).to_string(),
+            Some(Parameter::Op1Ratio) => format!("{:.2}", self.operators[0].ratio_multiplier()),
+            Some(Parameter::Op2Ratio) => format!("{:.2}", self.operators[1].ratio_multiplier()),
+            Some(Parameter::Op3Ratio) => format!("{:.2}", self.operators[2].ratio_multiplier()),
+            Some(Parameter::Op4Ratio) => format!("{:.2}", self.operators[3].ratio_multiplier()),
+            Some(Parameter::FilterCutoff) => format!("{:.0} Hz", self.filter.cutoff_hz()),
+            Some(Parameter::FilterResonance) => format!("{:.2}", self.filter.resonance_q()),
+            _ => format!("{:.3}", self.get_parameter(index)),
+        }
+    }
+
+    fn get_preset_name(&self, preset: i32) -> String {
+        FACTORY_PRESETS
+            .get(preset as usize)
+            .map(|preset| preset.name.to_string())
+            .unwrap_or_default()
+    }
+
+    fn change_preset(&self, preset: i32) {
+        if let Some(factory_preset) = FACTORY_PRESETS.get(preset as usize) {
+            for (index, value) in factory_preset_values(factory_preset).into_iter().enumerate() {
+                self.set_parameter(index as i32, value);
+            }
+
+            self.current_preset.store(preset, Ordering::Relaxed);
+        }
+    }
+
+    fn get_preset_num(&self) -> i32 {
+        self.current_preset.load(Ordering::Relaxed)
+    }
+
+    fn set_preset_num(&self, preset: i32) {
+        self.current_preset.store(preset, Ordering::Relaxed);
+    }
+
+    /// Serializes the live patch (not a factory preset) to a versioned blob.
+    fn get_preset_data(&self) -> Vec<u8> {
+        let values: Vec<f32> = (0..PARAMETER_COUNT as i32).map(|index| self.get_parameter(index)).collect();
+        serialize_parameters(&values)
+    }
+
+    fn set_preset_data(&self, data: &[u8]) {
+        let Some(values) = deserialize_parameters(data) else {
+            return;
+        };
+
+        for (index, value) in values.into_iter().take(PARAMETER_COUNT).enumerate() {
+            self.set_parameter(index as i32, value);
+        }
+    }
+
+    /// Savoy keeps a single live parameter set (factory presets are loaded
+    /// into it, not stored separately), so the bank is just that set's data.
+    fn get_bank_data(&self) -> Vec<u8> {
+        self.get_preset_data()
+    }
+
+    fn set_bank_data(&self, data: &[u8]) {
+        self.set_preset_data(data);
+    }
 }
 
 #[derive(FromPrimitive, Clone, Copy)]
 pub enum Parameter {
     Oscillator = 0,
-    Attack = 1,
-    Decay = 2,
-    Sustain = 3,
-    Release = 4,
+    Algorithm = 1,
+    Feedback = 2,
+    Op1Ratio = 3,
+    Op1Level = 4,
+    Op1Attack = 5,
+    Op1Decay = 6,
+    Op1Sustain = 7,
+    Op1Release = 8,
+    Op2Ratio = 9,
+    Op2Level = 10,
+    Op2Attack = 11,
+    Op2Decay = 12,
+    Op2Sustain = 13,
+    Op2Release = 14,
+    Op3Ratio = 15,
+    Op3Level = 16,
+    Op3Attack = 17,
+    Op3Decay = 18,
+    Op3Sustain = 19,
+    Op3Release = 20,
+    Op4Ratio = 21,
+    Op4Level = 22,
+    Op4Attack = 23,
+    Op4Decay = 24,
+    Op4Sustain = 25,
+    Op4Release = 26,
+    FilterCutoff = 27,
+    FilterResonance = 28,
+    FilterEnvelopeAmount = 29,
+    FilterAttack = 30,
+    FilterDecay = 31,
+    FilterSustain = 32,
+    FilterRelease = 33,
+    VelocitySensitivity = 34,
+}
+
+enum ParameterTarget {
+    Operator(usize, OperatorField),
+    Filter(FilterField),
+}
+
+impl Parameter {
+    /// Maps an `OpN*`/`Filter*` variant to the struct and field it reads
+    /// from; `None` for the global parameters handled separately.
+    fn target(self) -> Option<ParameterTarget> {
+        match self {
+            Parameter::Oscillator
+            | Parameter::Algorithm
+            | Parameter::Feedback
+            | Parameter::VelocitySensitivity => None,
+            Parameter::Op1Ratio => Some(ParameterTarget::Operator(0, OperatorField::Ratio)),
+            Parameter::Op1Level => Some(ParameterTarget::Operator(0, OperatorField::Level)),
+            Parameter::Op1Attack => Some(ParameterTarget::Operator(0, OperatorField::Attack)),
+            Parameter::Op1Decay => Some(ParameterTarget::Operator(0, OperatorField::Decay)),
+            Parameter::Op1Sustain => Some(ParameterTarget::Operator(0, OperatorField::Sustain)),
+            Parameter::Op1Release => Some(ParameterTarget::Operator(0, OperatorField::Release)),
+            Parameter::Op2Ratio => Some(ParameterTarget::Operator(1, OperatorField::Ratio)),
+            Parameter::Op2Level => Some(ParameterTarget::Operator(1, OperatorField::Level)),
+            Parameter::Op2Attack => Some(ParameterTarget::Operator(1, OperatorField::Attack)),
+            Parameter::Op2Decay => Some(ParameterTarget::Operator(1, OperatorField::Decay)),
+            Parameter::Op2Sustain => Some(ParameterTarget::Operator(1, OperatorField::Sustain)),
+            Parameter::Op2Release => Some(ParameterTarget::Operator(1, OperatorField::Release)),
+            Parameter::Op3Ratio => Some(ParameterTarget::Operator(2, OperatorField::Ratio)),
+            Parameter::Op3Level => Some(ParameterTarget::Operator(2, OperatorField::Level)),
+            Parameter::Op3Attack => Some(ParameterTarget::Operator(2, OperatorField::Attack)),
+            Parameter::Op3Decay => Some(ParameterTarget::Operator(2, OperatorField::Decay)),
+            Parameter::Op3Sustain => Some(ParameterTarget::Operator(2, OperatorField::Sustain)),
+            Parameter::Op3Release => Some(ParameterTarget::Operator(2, OperatorField::Release)),
+            Parameter::Op4Ratio => Some(ParameterTarget::Operator(3, OperatorField::Ratio)),
+            Parameter::Op4Level => Some(ParameterTarget::Operator(3, OperatorField::Level)),
+            Parameter::Op4Attack => Some(ParameterTarget::Operator(3, OperatorField::Attack)),
+            Parameter::Op4Decay => Some(ParameterTarget::Operator(3, OperatorField::Decay)),
+            Parameter::Op4Sustain => Some(ParameterTarget::Operator(3, OperatorField::Sustain)),
+            Parameter::Op4Release => Some(ParameterTarget::Operator(3, OperatorField::Release)),
+            Parameter::FilterCutoff => Some(ParameterTarget::Filter(FilterField::Cutoff)),
+            Parameter::FilterResonance => Some(ParameterTarget::Filter(FilterField::Resonance)),
+            Parameter::FilterEnvelopeAmount => Some(ParameterTarget::Filter(FilterField::EnvelopeAmount)),
+            Parameter::FilterAttack => Some(ParameterTarget::Filter(FilterField::Attack)),
+            Parameter::FilterDecay => Some(ParameterTarget::Filter(FilterField::Decay)),
+            Parameter::FilterSustain => Some(ParameterTarget::Filter(FilterField::Sustain)),
+            Parameter::FilterRelease => Some(ParameterTarget::Filter(FilterField::Release)),
+        }
+    }
 }
 
 impl Display for Parameter {
@@ -73,10 +561,40 @@ impl Display for Parameter {
             "{}",
             match self {
                 Parameter::Oscillator => "Oscillator",
-                Parameter::Attack => "Attack",
-                Parameter::Decay => "Decay",
-                Parameter::Sustain => "Sustain",
-                Parameter::Release => "Release",
+                Parameter::Algorithm => "Algorithm",
+                Parameter::Feedback => "Feedback",
+                Parameter::Op1Ratio => "Op1 Ratio",
+                Parameter::Op1Level => "Op1 Level",
+                Parameter::Op1Attack => "Op1 Attack",
+                Parameter::Op1Decay => "Op1 Decay",
+                Parameter::Op1Sustain => "Op1 Sustain",
+                Parameter::Op1Release => "Op1 Release",
+                Parameter::Op2Ratio => "Op2 Ratio",
+                Parameter::Op2Level => "Op2 Level",
+                Parameter::Op2Attack => "Op2 Attack",
+                Parameter::Op2Decay => "Op2 Decay",
+                Parameter::Op2Sustain => "Op2 Sustain",
+                Parameter::Op2Release => "Op2 Release",
+                Parameter::Op3Ratio => "Op3 Ratio",
+                Parameter::Op3Level => "Op3 Level",
+                Parameter::Op3Attack => "Op3 Attack",
+                Parameter::Op3Decay => "Op3 Decay",
+                Parameter::Op3Sustain => "Op3 Sustain",
+                Parameter::Op3Release => "Op3 Release",
+                Parameter::Op4Ratio => "Op4 Ratio",
+                Parameter::Op4Level => "Op4 Level",
+                Parameter::Op4Attack => "Op4 Attack",
+                Parameter::Op4Decay => "Op4 Decay",
+                Parameter::Op4Sustain => "Op4 Sustain",
+                Parameter::Op4Release => "Op4 Release",
+                Parameter::FilterCutoff => "Filter Cutoff",
+                Parameter::FilterResonance => "Filter Resonance",
+                Parameter::FilterEnvelopeAmount => "Filter Envelope Amount",
+                Parameter::FilterAttack => "Filter Attack",
+                Parameter::FilterDecay => "Filter Decay",
+                Parameter::FilterSustain => "Filter Sustain",
+                Parameter::FilterRelease => "Filter Release",
+                Parameter::VelocitySensitivity => "Velocity Sensitivity",
             }
         )
     }